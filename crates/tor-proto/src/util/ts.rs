@@ -6,8 +6,9 @@ use std::sync::atomic::{AtomicU64, Ordering};
 /// An object for determining whether an event happened,
 /// and if yes, when it happened.
 ///
-/// Every `Timestamp` has internal mutability.  A timestamp can move
-/// forward in time, but never backwards.
+/// Every `Timestamp` has internal mutability.  `update` and `update_to`
+/// only move a timestamp forward in time, never backwards;
+/// `compare_and_set` and `clear` can move it backwards or reset it.
 ///
 /// Internally, it uses the `coarsetime` crate to represent times in a way
 /// that lets us do atomic updates.
@@ -45,6 +46,35 @@ impl AtomicOptTimestamp {
             .compare_exchange(0, now, Ordering::Relaxed, Ordering::Relaxed);
     }
 
+    /// If the timestamp is currently `expected`, set it to `new`.
+    ///
+    /// (`None` means "never updated".)
+    ///
+    /// Return true if the timestamp was changed.
+    ///
+    /// Unlike [`update_to`](Self::update_to), this can move the timestamp backwards,
+    /// or clear it.
+    ///
+    /// Like the other methods here, this uses `Relaxed` ordering:
+    /// the timestamp is not used to synchronize access to any other data.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn compare_and_set(
+        &self,
+        expected: Option<coarsetime::Instant>,
+        new: Option<coarsetime::Instant>,
+    ) -> bool {
+        let ticks = |t: Option<coarsetime::Instant>| t.map_or(0, |t| t.as_ticks());
+
+        self.latest
+            .compare_exchange(
+                ticks(expected),
+                ticks(new),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
     /// Clear the timestamp and make it not updated again.
     pub(crate) fn clear(&self) {
         self.latest.store(0, Ordering::Relaxed);
@@ -138,6 +168,38 @@ mod test {
         assert!(ts.time_since_update_at(even_later).is_none());
     }
 
+    #[test]
+    fn compare_and_set() {
+        use coarsetime::{Duration, Instant};
+
+        let ts = AtomicOptTimestamp::new();
+        let first = Instant::now();
+        let later = first + Duration::from_secs(10);
+
+        // Wrong expectation: no change.
+        assert!(!ts.compare_and_set(Some(first), Some(later)));
+        assert!(ts.time_since_update_at(later).is_none());
+
+        assert!(ts.compare_and_set(None, Some(later)));
+        assert_eq!(ts.time_since_update_at(later), Some(Duration::from_secs(0)));
+
+        // Wrong expectation: no change.
+        assert!(!ts.compare_and_set(None, Some(first)));
+        assert!(!ts.compare_and_set(Some(first), None));
+        assert_eq!(ts.time_since_update_at(later), Some(Duration::from_secs(0)));
+
+        // Can move backwards.
+        assert!(ts.compare_and_set(Some(later), Some(first)));
+        assert_eq!(
+            ts.time_since_update_at(later),
+            Some(Duration::from_secs(10))
+        );
+
+        // Can clear.
+        assert!(ts.compare_and_set(Some(first), None));
+        assert!(ts.time_since_update_at(later).is_none());
+    }
+
     #[test]
     fn update_if_none() {
         let ts = AtomicOptTimestamp::new();
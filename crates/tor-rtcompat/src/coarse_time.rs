@@ -41,6 +41,7 @@
 //!  b2. abolish the newtypes and instead make the types
 //!    here aliases for coarsetime
 
+use std::sync::{Arc, Mutex};
 use std::time;
 
 use derive_more::{Add, AddAssign, Sub, SubAssign};
@@ -162,6 +163,66 @@ impl CoarseTimeProvider for RealCoarseTimeProvider {
     }
 }
 
+/// Provider of reduced-precision timestamps that only advance when told to
+///
+/// Useful for testing time-dependent code deterministically,
+/// without pulling in a whole mock runtime.
+///
+/// Clones share the same clock:
+/// advancing one advances them all.
+///
+/// (If you need sleeping, or mocked `Instant`s and wallclock too,
+/// use `tor-rtmock` instead.)
+#[derive(Clone, Debug)]
+pub struct ManualCoarseTimeProvider {
+    /// The current time, as reported by `now_coarse`
+    now: Arc<Mutex<CoarseInstant>>,
+}
+
+impl ManualCoarseTimeProvider {
+    /// Returns a new `ManualCoarseTimeProvider`, starting at the current real time
+    pub fn new() -> Self {
+        Self::new_at(RealCoarseTimeProvider::new().now_coarse())
+    }
+
+    /// Returns a new `ManualCoarseTimeProvider`, starting at `now`
+    pub fn new_at(now: CoarseInstant) -> Self {
+        ManualCoarseTimeProvider {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Advance the clock by `dur`
+    pub fn advance(&self, dur: CoarseDuration) {
+        *self.lock() += dur;
+    }
+
+    /// Set the clock to `now`
+    ///
+    /// `now` may be earlier than the current time;
+    /// this is a test facility, so we don't enforce monotonicity.
+    pub fn set(&self, now: CoarseInstant) {
+        *self.lock() = now;
+    }
+
+    /// Lock the current time
+    fn lock(&self) -> std::sync::MutexGuard<'_, CoarseInstant> {
+        self.now.lock().expect("coarse time poisoned")
+    }
+}
+
+impl Default for ManualCoarseTimeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoarseTimeProvider for ManualCoarseTimeProvider {
+    fn now_coarse(&self) -> CoarseInstant {
+        *self.lock()
+    }
+}
+
 #[cfg(not(miri))] // TODO coarse_time subtracts with overflow!
 #[cfg(test)]
 mod test {
@@ -191,4 +252,25 @@ mod test {
         assert!(t0 < t2);
         assert!(t1 < t2);
     }
+
+    #[test]
+    fn manual() {
+        let secs = |s| CoarseDuration::from(time::Duration::from_secs(s));
+
+        let p = ManualCoarseTimeProvider::new();
+        let t0 = p.now_coarse();
+        assert_eq!(p.now_coarse(), t0);
+
+        p.advance(secs(10));
+        assert_eq!(p.now_coarse(), t0 + secs(10));
+
+        // Clones share the clock.
+        let p2 = p.clone();
+        p2.advance(secs(5));
+        assert_eq!(p.now_coarse(), t0 + secs(15));
+        assert_eq!(p2.now_coarse(), t0 + secs(15));
+
+        p.set(t0 + secs(3));
+        assert_eq!(p2.now_coarse(), t0 + secs(3));
+    }
 }
@@ -74,7 +74,9 @@ pub use traits::{
     ToplevelRuntime, UdpProvider, UdpSocket, UnsupportedStreamOp,
 };
 
-pub use coarse_time::{CoarseDuration, CoarseInstant, RealCoarseTimeProvider};
+pub use coarse_time::{
+    CoarseDuration, CoarseInstant, ManualCoarseTimeProvider, RealCoarseTimeProvider,
+};
 pub use dyn_time::DynTimeProvider;
 pub use timer::{SleepProviderExt, Timeout, TimeoutError};
 